@@ -0,0 +1,114 @@
+/// Converts changes of leading whitespace to synthetic INDENT, DEDENT and NEWLINE tokens, like in Python.
+///
+/// Your grammar declares these 3 tokens as usual terminals, and the tokenizer calls `line()` at the beginning of every line that contains tokens,
+/// and `end()` at the end of input. The `Indenter` then feeds the synthetic tokens to the parser through the given callback:
+///
+/// * NEWLINE before every line except the first, and at the end of input.
+/// * INDENT if the line is indented deeper than the previous one.
+/// * One DEDENT for every indentation level that the line closes. DEDENTs come after NEWLINE.
+///
+/// Blank lines, and lines inside parentheses (if your language joins them) must not be reported to `line()`.
+///
+/// ```ignore
+/// let mut indenter = Indenter::new(Token::INDENT, Token::DEDENT, Token::NEWLINE, ());
+/// for line in input.lines().filter(|line| !line.trim().is_empty())
+/// {	let column = indenter.measure(line);
+/// 	indenter.line(column, |token, value| parser.add_token(token, value)).unwrap();
+/// 	// here feed the tokens from &line[column ..]
+/// }
+/// indenter.end(|token, value| parser.add_token(token, value)).unwrap();
+/// ```
+pub struct Indenter<T, V>
+{	indent: T,
+	dedent: T,
+	newline: T,
+	value: V,
+	tab_width: usize,
+	levels: Vec<usize>,
+	has_lines: bool,
+}
+
+/// Error returned from `Indenter::line()`.
+///
+/// After this error the parser and the indenter hold only part of the input, so don't reuse them for the next input, but create new ones.
+#[derive(Debug, Clone, PartialEq)]
+pub enum IndentError<E>
+{	/// The first line of input is indented. Like in Python, this is not allowed.
+	UnexpectedIndent {column: usize},
+	/// The line was dedented to a column, that doesn't match any enclosing indentation level.
+	InconsistentDedent {column: usize},
+	/// The callback (`parser.add_token()`) returned error.
+	Parser(E),
+}
+
+impl<T: Clone, V: Clone> Indenter<T, V>
+{	/// Creates indenter that will produce the given tokens. The `value` will be cloned and passed together with each synthetic token.
+	pub fn new(indent: T, dedent: T, newline: T, value: V) -> Self
+	{	Self {indent, dedent, newline, value, tab_width: 8, levels: Vec::new(), has_lines: false}
+	}
+
+	/// Sets the number of columns between tab stops, that `measure()` uses. Default is 8.
+	pub fn with_tab_width(mut self, tab_width: usize) -> Self
+	{	self.tab_width = tab_width.max(1);
+		self
+	}
+
+	/// Returns the indentation column of the line, counting spaces and tabs at the line beginning.
+	pub fn measure(&self, line: &str) -> usize
+	{	let mut column = 0;
+		for c in line.bytes()
+		{	match c
+			{	b' ' => column += 1,
+				b'\t' => column += self.tab_width - column%self.tab_width,
+				_ => break
+			}
+		}
+		column
+	}
+
+	/// Number of currently open indentation levels.
+	pub fn depth(&self) -> usize
+	{	self.levels.len()
+	}
+
+	/// Call this at the beginning of each line that contains tokens, before feeding them. The `column` is typically found with `measure()`.
+	/// If the first line is indented, or the dedent is inconsistent, returns error without calling `add_token`.
+	pub fn line<E, F>(&mut self, column: usize, mut add_token: F) -> Result<(), IndentError<E>> where F: FnMut(T, V) -> Result<(), E>
+	{	if !self.has_lines && column > 0
+		{	return Err(IndentError::UnexpectedIndent {column});
+		}
+		let n_levels = self.levels.iter().take_while(|&&level| level <= column).count();
+		let current = if n_levels == 0 {0} else {self.levels[n_levels - 1]};
+		if column > current && n_levels < self.levels.len()
+		{	return Err(IndentError::InconsistentDedent {column});
+		}
+		if self.has_lines
+		{	add_token(self.newline.clone(), self.value.clone()).map_err(IndentError::Parser)?;
+		}
+		self.has_lines = true;
+		if column > current
+		{	self.levels.push(column);
+			add_token(self.indent.clone(), self.value.clone()).map_err(IndentError::Parser)?;
+		}
+		else
+		{	while self.levels.len() > n_levels
+			{	self.levels.pop();
+				add_token(self.dedent.clone(), self.value.clone()).map_err(IndentError::Parser)?;
+			}
+		}
+		Ok(())
+	}
+
+	/// Call this at the end of input. Feeds final NEWLINE (if there were lines), and DEDENT for each open level.
+	/// After this the indenter is ready for the next input.
+	pub fn end<E, F>(&mut self, mut add_token: F) -> Result<(), E> where F: FnMut(T, V) -> Result<(), E>
+	{	if self.has_lines
+		{	self.has_lines = false;
+			add_token(self.newline.clone(), self.value.clone())?;
+		}
+		while self.levels.pop().is_some()
+		{	add_token(self.dedent.clone(), self.value.clone())?;
+		}
+		Ok(())
+	}
+}
@@ -67,6 +67,13 @@
 //!
 //! To specify Lemon parser directives, like `%token_type {f64}`, need to use `#[lem_opt()]` attributes near start symbol, like `#[lem_opt(token_type="f64")]`.
 //!
+//! Operator precedence and associativity are declared with `left`, `right` and `nonassoc`, like `#[lem_opt(nonassoc="EQ NE", left="PLUS MINUS", right="POW")]`.
+//! These correspond to Lemon's `%left`, `%right` and `%nonassoc` directives. Each of them can appear multiple times, and precedence levels go in the order of declaration,
+//! from the lowest to the highest. With `right` the expression `a ^ b ^ c` means `a ^ (b ^ c)`, and with `nonassoc` the expression `a == b == c` is syntax error.
+//!
+//! Keywords that can also be used as identifiers, where they don't fit the grammar, are declared with `fallback`, like `#[lem_opt(fallback="ID KW1 KW2")]`.
+//! This corresponds to Lemon's `%fallback ID KW1 KW2.`: the first token is the one, that the others fall back to.
+//!
 //! Here is complete example:
 //!
 //! ```
@@ -191,11 +198,26 @@
 //! }
 //! ```
 //!
+//! Besides the parser generator, this crate contains several helpers:
+//! * [TokenRecorder] - records tokens fed to a parser, and replays them later.
+//! * [Indenter] - produces INDENT, DEDENT and NEWLINE tokens for indentation-sensitive languages.
+//! * [TokenFilter] - stage between tokenizer and parser, that can add, remove or replace tokens. Filters can be connected with [FilterChain].
+//! * [assert_tree_eq!] and [tree_diff] - compare parse results in tests, and show readable diff of the trees, if they differ.
 
 extern crate lemon_tree_derive;
 
 pub use lemon_tree_derive::{lem_fn, LemonTree, LemonTreeNode};
 
+mod token_recorder;
+mod indenter;
+mod token_filter;
+mod tree_diff;
+
+pub use token_recorder::TokenRecorder;
+pub use indenter::{Indenter, IndentError};
+pub use token_filter::{TokenFilter, FilterChain};
+pub use tree_diff::tree_diff;
+
 /// Parser "start symbol" can be represented as a struct or enum. You need to annotate it with `#[derive(LemonTree)]`, and implementation of this trait will be generated.
 ///
 /// The implementation contains 2 associated types:
@@ -0,0 +1,65 @@
+/// Stage between tokenizer and parser, that receives tokens, and passes zero or more tokens further.
+/// Can be used to implement macro expansion, include handling, comment-directive processing, etc.
+///
+/// Filters can be chained with `FilterChain`, and the last stage is typically `parser.add_token()`:
+///
+/// ```ignore
+/// let mut filters = FilterChain::new(ExpandMacros::new(), SkipComments);
+/// filters.token(Token::IDENT, value, &mut |token, value| parser.add_token(token, value))?;
+/// ...
+/// filters.end(&mut |token, value| parser.add_token(token, value))?;
+/// let result = parser.end()?;
+/// ```
+///
+/// `E` is the error type of the final stage. Filters that don't fail implement this trait for any `E`,
+/// and filters that have their own errors can require `E: From<MyError>`.
+///
+/// Filters can't see which tokens the parser would accept next, because the generated Parser doesn't expose this information.
+///
+/// Closures of type `FnMut(T, V, &mut dyn FnMut(T, V) -> Result<(), E>) -> Result<(), E>` are filters as well.
+pub trait TokenFilter<T, V, E>
+{	/// Receives one token, and passes zero or more tokens to `emit`.
+	fn token(&mut self, token: T, value: V, emit: &mut dyn FnMut(T, V) -> Result<(), E>) -> Result<(), E>;
+
+	/// Called at the end of input, to pass tokens that the filter holds back, if any.
+	fn end(&mut self, _emit: &mut dyn FnMut(T, V) -> Result<(), E>) -> Result<(), E>
+	{	Ok(())
+	}
+}
+
+impl<T, V, E, F> TokenFilter<T, V, E> for F where F: FnMut(T, V, &mut dyn FnMut(T, V) -> Result<(), E>) -> Result<(), E>
+{	fn token(&mut self, token: T, value: V, emit: &mut dyn FnMut(T, V) -> Result<(), E>) -> Result<(), E>
+	{	self(token, value, emit)
+	}
+}
+
+/// Two filters, connected one after another.
+pub struct FilterChain<A, B>
+{	first: A,
+	second: B,
+}
+
+impl<A, B> FilterChain<A, B>
+{	/// Creates filter that passes tokens through `first`, and then its output through `second`.
+	pub fn new(first: A, second: B) -> Self
+	{	Self {first, second}
+	}
+
+	/// Appends one more filter to the chain.
+	pub fn chain<C>(self, next: C) -> FilterChain<Self, C>
+	{	FilterChain {first: self, second: next}
+	}
+}
+
+impl<T, V, E, A, B> TokenFilter<T, V, E> for FilterChain<A, B> where A: TokenFilter<T, V, E>, B: TokenFilter<T, V, E>
+{	fn token(&mut self, token: T, value: V, emit: &mut dyn FnMut(T, V) -> Result<(), E>) -> Result<(), E>
+	{	let second = &mut self.second;
+		self.first.token(token, value, &mut |token, value| second.token(token, value, emit))
+	}
+
+	fn end(&mut self, emit: &mut dyn FnMut(T, V) -> Result<(), E>) -> Result<(), E>
+	{	let second = &mut self.second;
+		self.first.end(&mut |token, value| second.token(token, value, emit))?;
+		self.second.end(emit)
+	}
+}
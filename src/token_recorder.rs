@@ -0,0 +1,105 @@
+/// Captures the exact sequence of `(token, value, span)` that was fed to a parser, so it can be replayed later.
+///
+/// Use it to reproduce a parse failure without having the original source: let the tokenizer feed the recorder instead of (or in addition to) the parser,
+/// save the entries, and later replay them to a fresh parser.
+///
+/// The span type `S` is whatever your tokenizer uses to describe positions, and defaults to `()`.
+///
+/// ```ignore
+/// let mut recorder = TokenRecorder::new();
+/// recorder.record(<Unit as LemonTree>::Token::VALUE, 10.0, 0..2);
+/// recorder.record(<Unit as LemonTree>::Token::PLUS, 0.0, 3..4);
+///
+/// let mut parser = Unit::get_parser(());
+/// match recorder.replay(|token, value, _span| parser.add_token(token, value))
+/// {	Ok(()) => println!("Result: {:?}", parser.end()),
+/// 	Err((n, _error)) => println!("Parser rejected token #{}", n),
+/// }
+/// ```
+///
+/// The recorder doesn't serialize itself, because the generated `Token` enum doesn't implement `Serialize` or any other serialization trait.
+/// To save a recording, serialize `entries()` in your preferred format, converting each token to a representation of your choice.
+/// To load it back, collect the entries into `TokenRecorder` (it implements `FromIterator` and `From<Vec<(T, V, S)>>`).
+#[derive(Debug, Clone, PartialEq)]
+pub struct TokenRecorder<T, V, S=()>
+{	entries: Vec<(T, V, S)>,
+}
+
+impl<T, V, S> TokenRecorder<T, V, S>
+{	/// Creates empty recorder.
+	pub fn new() -> Self
+	{	Self {entries: Vec::new()}
+	}
+
+	/// Appends one token to the recording.
+	pub fn record(&mut self, token: T, value: V, span: S)
+	{	self.entries.push((token, value, span));
+	}
+
+	/// Records the token, and then passes it to `add_token`, that is expected to call `parser.add_token()`.
+	/// Returns what `add_token` returned. The token is recorded even if the parser rejects it, so the failing token will be the last in the recording.
+	pub fn record_and_pass<E, F>(&mut self, token: T, value: V, span: S, add_token: F) -> Result<(), E> where T: Clone, V: Clone, S: Clone, F: FnOnce(T, V, S) -> Result<(), E>
+	{	self.entries.push((token.clone(), value.clone(), span.clone()));
+		add_token(token, value, span)
+	}
+
+	/// Returns the recorded tokens in the order they were recorded.
+	pub fn entries(&self) -> &[(T, V, S)]
+	{	&self.entries
+	}
+
+	/// Consumes the recorder, and returns the recorded tokens.
+	pub fn into_entries(self) -> Vec<(T, V, S)>
+	{	self.entries
+	}
+
+	/// Number of recorded tokens.
+	pub fn len(&self) -> usize
+	{	self.entries.len()
+	}
+
+	/// Returns true if no tokens were recorded.
+	pub fn is_empty(&self) -> bool
+	{	self.entries.is_empty()
+	}
+
+	/// Removes all the recorded tokens, so the recorder can be reused.
+	pub fn clear(&mut self)
+	{	self.entries.clear();
+	}
+
+	/// Feeds all the recorded tokens to `add_token` in the recorded order, stopping at the first error.
+	/// On error returns the index of the rejected entry, together with the error.
+	pub fn replay<E, F>(self, mut add_token: F) -> Result<(), (usize, E)> where F: FnMut(T, V, S) -> Result<(), E>
+	{	for (n, (token, value, span)) in self.entries.into_iter().enumerate()
+		{	add_token(token, value, span).map_err(|e| (n, e))?;
+		}
+		Ok(())
+	}
+
+	/// Like `replay()`, but keeps the recording, so it can be replayed again.
+	pub fn replay_cloned<E, F>(&self, mut add_token: F) -> Result<(), (usize, E)> where T: Clone, V: Clone, S: Clone, F: FnMut(T, V, S) -> Result<(), E>
+	{	for (n, (token, value, span)) in self.entries.iter().enumerate()
+		{	add_token(token.clone(), value.clone(), span.clone()).map_err(|e| (n, e))?;
+		}
+		Ok(())
+	}
+}
+
+impl<T, V, S> Default for TokenRecorder<T, V, S>
+{	fn default() -> Self
+	{	Self::new()
+	}
+}
+
+impl<T, V, S> From<Vec<(T, V, S)>> for TokenRecorder<T, V, S>
+{	fn from(entries: Vec<(T, V, S)>) -> Self
+	{	Self {entries}
+	}
+}
+
+impl<T, V, S> std::iter::FromIterator<(T, V, S)> for TokenRecorder<T, V, S>
+{	fn from_iter<I: IntoIterator<Item=(T, V, S)>>(iter: I) -> Self
+	{	Self {entries: iter.into_iter().collect()}
+	}
+}
@@ -0,0 +1,163 @@
+use std::fmt::{self, Debug, Write};
+
+const CONTEXT_LINES: usize = 3;
+
+/// Compares 2 parse results, and if they differ, returns readable line-by-line diff of their pretty-printed (`{:#?}`) representations.
+/// Lines that present only in `left` are marked with `-`, and lines that present only in `right` with `+`.
+/// Unchanged parts of the tree, that are far from changes, are skipped.
+///
+/// The comparison is textual: it works on `Debug` output, and requires `PartialEq + Debug`.
+/// It doesn't know the grammar's node structure, because this is only known to the derive macros.
+/// Pretty-printed `Debug` puts every node on its own lines, so the diff still follows the tree shape.
+///
+/// The diff takes linear memory, and time proportional to the input size multiplied by the number of different lines,
+/// so large trees that differ in few places are compared quickly.
+///
+/// Usually you will use `assert_tree_eq!()`, that panics with this diff.
+pub fn tree_diff<T>(left: &T, right: &T) -> Option<String> where T: PartialEq + Debug + ?Sized
+{	if left == right
+	{	return None;
+	}
+	let left = format!("{:#?}", left);
+	let right = format!("{:#?}", right);
+	if left == right
+	{	return Some("Values are not equal, but their Debug representations are the same\n".to_string());
+	}
+	let left: Vec<&str> = left.lines().collect();
+	let right: Vec<&str> = right.lines().collect();
+	let mut lines = Vec::with_capacity(left.len().max(right.len()));
+	diff_lines(&left, &right, &mut lines);
+	let mut diff = String::new();
+	write_diff(&mut diff, &lines).unwrap();
+	Some(diff)
+}
+
+/// Appends to `lines` the shortest edit script that converts `left` to `right`, as pairs of mark (' ', '-' or '+') and line.
+fn diff_lines<'a>(left: &[&'a str], right: &[&'a str], lines: &mut Vec<(char, &'a str)>)
+{	// common prefix and suffix
+	let prefix = left.iter().zip(right).take_while(|(a, b)| a == b).count();
+	let suffix = left[prefix ..].iter().rev().zip(right[prefix ..].iter().rev()).take_while(|(a, b)| a == b).count();
+	lines.extend(left[.. prefix].iter().map(|&line| (' ', line)));
+	let left_middle = &left[prefix .. left.len()-suffix];
+	let right_middle = &right[prefix .. right.len()-suffix];
+	// the middle part
+	if left_middle.is_empty() || right_middle.is_empty()
+	{	lines.extend(left_middle.iter().map(|&line| ('-', line)));
+		lines.extend(right_middle.iter().map(|&line| ('+', line)));
+	}
+	else if let Some((x, y)) = find_split(left_middle, right_middle)
+	{	diff_lines(&left_middle[.. x], &right_middle[.. y], lines);
+		diff_lines(&left_middle[x ..], &right_middle[y ..], lines);
+	}
+	else
+	{	lines.extend(left_middle.iter().map(|&line| ('-', line)));
+		lines.extend(right_middle.iter().map(|&line| ('+', line)));
+	}
+	lines.extend(left[left.len()-suffix ..].iter().map(|&line| (' ', line)));
+}
+
+/// Myers' bisection: walks shortest edit paths from both ends at once, and returns the point where they meet.
+/// The diff of the 2 halves at this point gives the shortest edit script of the whole.
+/// Uses memory proportional to the input size. Returns `None` if the inputs have no common lines.
+fn find_split(left: &[&str], right: &[&str]) -> Option<(usize, usize)>
+{	let (n, m) = (left.len() as isize, right.len() as isize);
+	let max_d = (n + m + 1) / 2;
+	let offset = max_d + 1;
+	let v_len = (2*max_d + 3) as usize;
+	// forward[offset + k] is the furthest x reached on diagonal k (x - y == k) from the beginning,
+	// and backward[offset + k] is the same from the end, where x and y count from the end
+	let mut forward = vec![-1isize; v_len];
+	let mut backward = vec![-1isize; v_len];
+	forward[(offset + 1) as usize] = 0;
+	backward[(offset + 1) as usize] = 0;
+	let delta = n - m;
+	let is_odd = delta % 2 != 0;
+	// diagonals that went out of the rectangle are excluded from further walking
+	let (mut k1_start, mut k1_end, mut k2_start, mut k2_end) = (0, 0, 0, 0);
+	for d in 0 .. max_d
+	{	let mut k1 = -d + k1_start;
+		while k1 <= d - k1_end
+		{	let i = (offset + k1) as usize;
+			let mut x = if k1 == -d || k1 != d && forward[i - 1] < forward[i + 1] {forward[i + 1]} else {forward[i - 1] + 1};
+			let mut y = x - k1;
+			while x<n && y<m && left[x as usize]==right[y as usize]
+			{	x += 1;
+				y += 1;
+			}
+			forward[i] = x;
+			if x > n
+			{	k1_end += 2;
+			}
+			else if y > m
+			{	k1_start += 2;
+			}
+			else if is_odd
+			{	let j = offset + delta - k1;
+				if j>=0 && j<v_len as isize && backward[j as usize]!=-1 && x >= n - backward[j as usize]
+				{	return Some((x as usize, y as usize));
+				}
+			}
+			k1 += 2;
+		}
+		let mut k2 = -d + k2_start;
+		while k2 <= d - k2_end
+		{	let i = (offset + k2) as usize;
+			let mut x = if k2 == -d || k2 != d && backward[i - 1] < backward[i + 1] {backward[i + 1]} else {backward[i - 1] + 1};
+			let mut y = x - k2;
+			while x<n && y<m && left[(n - x - 1) as usize]==right[(m - y - 1) as usize]
+			{	x += 1;
+				y += 1;
+			}
+			backward[i] = x;
+			if x > n
+			{	k2_end += 2;
+			}
+			else if y > m
+			{	k2_start += 2;
+			}
+			else if !is_odd
+			{	let j = offset + delta - k2;
+				if j>=0 && j<v_len as isize && forward[j as usize]!=-1
+				{	let x1 = forward[j as usize];
+					let y1 = x1 - (j - offset);
+					if x1 >= n - x
+					{	return Some((x1 as usize, y1 as usize));
+					}
+				}
+			}
+			k2 += 2;
+		}
+	}
+	None
+}
+
+/// Prints changed lines with some context around them.
+fn write_diff(out: &mut String, lines: &[(char, &str)]) -> fmt::Result
+{	let is_near_change = |n: usize| lines[n.saturating_sub(CONTEXT_LINES) .. (n + CONTEXT_LINES + 1).min(lines.len())].iter().any(|&(mark, _)| mark != ' ');
+	let mut skipped = false;
+	for (n, &(mark, line)) in lines.iter().enumerate()
+	{	if is_near_change(n)
+		{	writeln!(out, "{} {}", mark, line)?;
+			skipped = false;
+		}
+		else if !skipped
+		{	writeln!(out, "  ...")?;
+			skipped = true;
+		}
+	}
+	Ok(())
+}
+
+/// Like `assert_eq!()`, but on failure prints diff of the 2 trees, as returned by `tree_diff()`.
+///
+/// ```ignore
+/// assert_tree_eq!(parser.end().unwrap(), Unit {expr: Expr::Value(10.0)});
+/// ```
+#[macro_export]
+macro_rules! assert_tree_eq
+{	($left:expr, $right:expr $(,)?) =>
+	{	if let Some(diff) = $crate::tree_diff(&$left, &$right)
+		{	panic!("assertion failed: trees are not equal (- left, + right):\n{}", diff);
+		}
+	};
+}
@@ -0,0 +1,57 @@
+use lemon_tree::{lem_fn, LemonTree};
+
+type Expr = String;
+
+#[lem_fn("NUM(value)")] pub fn expr_1(value: String) -> Expr {value}
+#[lem_fn("Expr(a) EQ Expr(b)")] pub fn expr_2(a: Expr, b: Expr) -> Expr {format!("({} == {})", a, b)}
+#[lem_fn("Expr(a) PLUS Expr(b)")] pub fn expr_3(a: Expr, b: Expr) -> Expr {format!("({} + {})", a, b)}
+#[lem_fn("Expr(a) POW Expr(b)")] pub fn expr_4(a: Expr, b: Expr) -> Expr {format!("({} ^ {})", a, b)}
+
+#[derive(LemonTree)]
+#[lem_opt(token_type="String", nonassoc="EQ", left="PLUS", right="POW")]
+#[lem("Expr(expr)")]
+pub struct Program
+{	expr: Expr,
+}
+
+fn parse(parser: &mut <Program as LemonTree>::Parser, mut input: &str) -> Result<Expr, ()>
+{	loop
+	{	input = input.trim_start();
+		match input.bytes().next()
+		{	Some(c) => match c
+			{	b'+' => parser.add_token(<Program as LemonTree>::Token::PLUS, String::new()).map_err(|_| ())?,
+				b'^' => parser.add_token(<Program as LemonTree>::Token::POW, String::new()).map_err(|_| ())?,
+				b'=' =>
+				{	parser.add_token(<Program as LemonTree>::Token::EQ, String::new()).map_err(|_| ())?;
+					input = &input[1 ..]; // skip the second '='
+				}
+				b'0' ..= b'9' =>
+				{	let pos = input.bytes().position(|c| !c.is_ascii_digit()).unwrap_or(input.len());
+					parser.add_token(<Program as LemonTree>::Token::NUM, input[.. pos].to_string()).map_err(|_| ())?;
+					input = &input[pos-1 ..];
+				}
+				_ => panic!("Invalid token")
+			}
+			None =>
+			{	return parser.end().map(|program| program.expr).map_err(|_| ());
+			}
+		}
+		input = &input[1 ..];
+	}
+}
+
+#[test]
+fn assoc_1()
+{	let mut parser = Program::get_parser(());
+
+	// right associativity
+	assert_eq!(parse(&mut parser, "1 ^ 2 ^ 3"), Ok("(1 ^ (2 ^ 3))".to_string()));
+	// left associativity
+	assert_eq!(parse(&mut parser, "1 + 2 + 3"), Ok("((1 + 2) + 3)".to_string()));
+	// precedence levels go in the order of declaration
+	assert_eq!(parse(&mut parser, "1 + 2 ^ 3 == 4"), Ok("((1 + (2 ^ 3)) == 4)".to_string()));
+
+	// nonassoc operator can not be chained
+	let mut parser = Program::get_parser(());
+	assert_eq!(parse(&mut parser, "1 == 2 == 3"), Err(()));
+}
@@ -0,0 +1,38 @@
+use lemon_tree::{lem_fn, LemonTree};
+
+type Stmt = String;
+type Stmts = Vec<String>;
+
+#[lem_fn("ID(name) SEMICOLON")] pub fn stmt_1(name: String) -> Stmt {name}
+#[lem_fn("KW1 ID(name) SEMICOLON")] pub fn stmt_2(name: String) -> Stmt {format!("kw1 {}", name)}
+#[lem_fn("KW2 ID(name) SEMICOLON")] pub fn stmt_3(name: String) -> Stmt {format!("kw2 {}", name)}
+#[lem_fn("Stmt(item)")] pub fn stmts_1(item: Stmt) -> Stmts {vec![item]}
+#[lem_fn("Stmts(items) Stmt(item)")] pub fn stmts_2(mut items: Stmts, item: Stmt) -> Stmts {items.push(item); items}
+
+#[derive(LemonTree)]
+#[lem_opt(token_type="String", fallback="ID KW1 KW2")]
+#[lem("Stmts(stmts)")]
+pub struct Program
+{	stmts: Stmts,
+}
+
+fn parse(parser: &mut <Program as LemonTree>::Parser, input: &str) -> Stmts
+{	for word in input.split_whitespace()
+	{	match word
+		{	"kw1" => parser.add_token(<Program as LemonTree>::Token::KW1, word.to_string()).unwrap(),
+			"kw2" => parser.add_token(<Program as LemonTree>::Token::KW2, word.to_string()).unwrap(),
+			";" => parser.add_token(<Program as LemonTree>::Token::SEMICOLON, word.to_string()).unwrap(),
+			_ => parser.add_token(<Program as LemonTree>::Token::ID, word.to_string()).unwrap(),
+		}
+	}
+	parser.end().unwrap().stmts
+}
+
+#[test]
+fn fallback_1()
+{	let mut parser = Program::get_parser(());
+
+	assert_eq!(parse(&mut parser, "a ; kw1 b ; kw2 c ;"), vec!["a", "kw1 b", "kw2 c"]);
+	// keywords where they don't fit the grammar are treated as ID
+	assert_eq!(parse(&mut parser, "kw1 kw2 ; kw2 kw1 ; kw1 kw1 ;"), vec!["kw1 kw2", "kw2 kw1", "kw1 kw1"]);
+}
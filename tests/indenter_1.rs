@@ -0,0 +1,49 @@
+use lemon_tree::{lem_fn, LemonTree, Indenter, IndentError};
+
+type Stmt = String;
+type Stmts = Vec<String>;
+
+#[lem_fn("NAME(name) NEWLINE")] pub fn stmt_1(name: String) -> Stmt {name}
+#[lem_fn("NAME(name) COLON NEWLINE INDENT Stmts(body) DEDENT")] pub fn stmt_2(name: String, body: Stmts) -> Stmt {format!("{}({})", name, body.join(" "))}
+#[lem_fn("Stmt(item)")] pub fn stmts_1(item: Stmt) -> Stmts {vec![item]}
+#[lem_fn("Stmts(items) Stmt(item)")] pub fn stmts_2(mut items: Stmts, item: Stmt) -> Stmts {items.push(item); items}
+
+#[derive(LemonTree)]
+#[lem_opt(token_type="String")]
+#[lem("Stmts(stmts)")]
+pub struct Program
+{	stmts: Stmts,
+}
+
+fn parse(parser: &mut <Program as LemonTree>::Parser, input: &str) -> Result<Stmts, IndentError<()>>
+{	let mut indenter = Indenter::new(<Program as LemonTree>::Token::INDENT, <Program as LemonTree>::Token::DEDENT, <Program as LemonTree>::Token::NEWLINE, String::new()).with_tab_width(4);
+	for line in input.lines().filter(|line| !line.trim().is_empty())
+	{	let column = indenter.measure(line);
+		indenter.line(column, |token, value| parser.add_token(token, value).map_err(|_| ()))?;
+		for word in line.split_whitespace()
+		{	if word.ends_with(':')
+			{	parser.add_token(<Program as LemonTree>::Token::NAME, word[.. word.len()-1].to_string()).unwrap();
+				parser.add_token(<Program as LemonTree>::Token::COLON, String::new()).unwrap();
+			}
+			else
+			{	parser.add_token(<Program as LemonTree>::Token::NAME, word.to_string()).unwrap();
+			}
+		}
+	}
+	indenter.end(|token, value| parser.add_token(token, value)).unwrap();
+	Ok(parser.end().unwrap().stmts)
+}
+
+#[test]
+fn indenter_1()
+{	let mut parser = Program::get_parser(());
+
+	assert_eq!(parse(&mut parser, "a:\n  b\n\n  c\nd\n"), Ok(vec!["a(b c)".to_string(), "d".to_string()]));
+	assert_eq!(parse(&mut parser, "a:\n\tb:\n\t\tc\n    d\n"), Ok(vec!["a(b(c) d)".to_string()]));
+
+	// after error the parser holds partial input, so each error case gets its own parser
+	let mut parser = Program::get_parser(());
+	assert_eq!(parse(&mut parser, "a:\n    b:\n        c\n  d\n"), Err(IndentError::InconsistentDedent {column: 2}));
+	let mut parser = Program::get_parser(());
+	assert_eq!(parse(&mut parser, "  a\nb\n"), Err(IndentError::UnexpectedIndent {column: 2}));
+}
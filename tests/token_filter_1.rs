@@ -0,0 +1,82 @@
+use lemon_tree::{lem_fn, LemonTree, TokenFilter, FilterChain};
+
+type Expr = f64;
+type Token = <Program as LemonTree>::Token;
+
+#[lem_fn("NUM(value)")] pub fn expr_1(value: f64) -> Expr {value}
+#[lem_fn("Expr(a) PLUS Expr(b)")] pub fn expr_2(a: Expr, b: Expr) -> Expr {a + b}
+#[lem_fn("Expr(a) TIMES Expr(b)")] pub fn expr_3(a: Expr, b: Expr) -> Expr {a * b}
+#[lem_fn("PAR_OPEN Expr(a) PAR_CLOSE")] pub fn expr_4(a: Expr) -> Expr {a}
+
+#[derive(LemonTree)]
+#[lem_opt(token_type="f64", left="PLUS", left="TIMES")]
+#[lem("Expr(expr)")]
+pub struct Program
+{	expr: Expr,
+}
+
+/// Inserts TIMES between adjacent operands, so "2(3+4)" means "2*(3+4)".
+struct ImplicitTimes
+{	after_operand: bool,
+}
+
+impl<E> TokenFilter<Token, f64, E> for ImplicitTimes
+{	fn token(&mut self, token: Token, value: f64, emit: &mut dyn FnMut(Token, f64) -> Result<(), E>) -> Result<(), E>
+	{	let (starts_operand, ends_operand) = match token
+		{	Token::NUM => (true, true),
+			Token::PAR_OPEN => (true, false),
+			Token::PAR_CLOSE => (false, true),
+			_ => (false, false)
+		};
+		if starts_operand && self.after_operand
+		{	emit(Token::TIMES, 0.0)?;
+		}
+		self.after_operand = ends_operand;
+		emit(token, value)
+	}
+
+	fn end(&mut self, _emit: &mut dyn FnMut(Token, f64) -> Result<(), E>) -> Result<(), E>
+	{	self.after_operand = false;
+		Ok(())
+	}
+}
+
+fn parse(parser: &mut <Program as LemonTree>::Parser, mut input: &str) -> (Expr, usize)
+{	let mut n_tokens = 0;
+	let counter = |token, value, emit: &mut dyn FnMut(Token, f64) -> Result<(), ()>| {n_tokens += 1; emit(token, value)};
+	let mut filters = FilterChain::new(ImplicitTimes {after_operand: false}, counter);
+	let mut add_token = |token, value| parser.add_token(token, value).map_err(|_| ());
+	loop
+	{	input = input.trim_start();
+		match input.bytes().next()
+		{	Some(c) => match c
+			{	b'+' => filters.token(Token::PLUS, 0.0, &mut add_token).unwrap(),
+				b'*' => filters.token(Token::TIMES, 0.0, &mut add_token).unwrap(),
+				b'(' => filters.token(Token::PAR_OPEN, 0.0, &mut add_token).unwrap(),
+				b')' => filters.token(Token::PAR_CLOSE, 0.0, &mut add_token).unwrap(),
+				b'0' ..= b'9' | b'.' =>
+				{	let pos = input.bytes().position(|c| !c.is_ascii_digit() && c!=b'.').unwrap_or(input.len());
+					let value = input[.. pos].parse().unwrap();
+					filters.token(Token::NUM, value, &mut add_token).unwrap();
+					input = &input[pos-1 ..];
+				}
+				_ => panic!("Invalid token")
+			}
+			None =>
+			{	filters.end(&mut add_token).unwrap();
+				break;
+			}
+		}
+		input = &input[1 ..];
+	}
+	(parser.end().unwrap().expr, n_tokens)
+}
+
+#[test]
+fn token_filter_1()
+{	let mut parser = Program::get_parser(());
+
+	assert_eq!(parse(&mut parser, "2 + 2 * 2"), (6.0, 5));
+	assert_eq!(parse(&mut parser, "2(3+4)"), (14.0, 7));
+	assert_eq!(parse(&mut parser, "(1+1)(2+2)3"), (24.0, 13));
+}
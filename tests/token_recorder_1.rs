@@ -0,0 +1,59 @@
+use lemon_tree::{lem_fn, LemonTree, TokenRecorder};
+
+type Expr = f64;
+
+#[lem_fn("NUM(value)")] pub fn expr_1(value: f64) -> Expr {value}
+#[lem_fn("Expr(a) PLUS Expr(b)")] pub fn expr_2(a: Expr, b: Expr) -> Expr {a + b}
+#[lem_fn("Expr(a) TIMES Expr(b)")] pub fn expr_3(a: Expr, b: Expr) -> Expr {a * b}
+
+#[derive(LemonTree)]
+#[lem_opt(token_type="f64", left="PLUS", left="TIMES")]
+#[lem("Expr(expr)")]
+pub struct Program
+{	expr: Expr,
+}
+
+fn tokenize(mut input: &str) -> TokenRecorder<<Program as LemonTree>::Token, f64, usize>
+{	let mut recorder = TokenRecorder::new();
+	let mut offset = 0;
+	loop
+	{	let len = input.len();
+		input = input.trim_start();
+		offset += len - input.len();
+		match input.bytes().next()
+		{	Some(c) => match c
+			{	b'+' => recorder.record(<Program as LemonTree>::Token::PLUS, 0.0, offset),
+				b'*' => recorder.record(<Program as LemonTree>::Token::TIMES, 0.0, offset),
+				b'0' ..= b'9' | b'.' =>
+				{	let pos = input.bytes().position(|c| !c.is_ascii_digit() && c!=b'.').unwrap_or(input.len());
+					let value = input[.. pos].parse().unwrap();
+					recorder.record(<Program as LemonTree>::Token::NUM, value, offset);
+					input = &input[pos-1 ..];
+					offset += pos - 1;
+				}
+				_ => panic!("Invalid token")
+			}
+			None =>
+			{	return recorder;
+			}
+		}
+		input = &input[1 ..];
+		offset += 1;
+	}
+}
+
+#[test]
+fn token_recorder_1()
+{	let recorder = tokenize("2 + 2 * 2");
+	assert_eq!(recorder.len(), 5);
+	assert_eq!(recorder.entries().iter().map(|e| e.2).collect::<Vec<_>>(), vec![0, 2, 4, 6, 8]);
+
+	let mut parser = Program::get_parser(());
+	recorder.replay(|token, value, _offset| parser.add_token(token, value)).ok().unwrap();
+	assert_eq!(parser.end().unwrap().expr, 6.0);
+
+	// the position of the rejected token is reported
+	let mut parser = Program::get_parser(());
+	let result = tokenize("2 + * 2").replay(|token, value, _offset| parser.add_token(token, value));
+	assert_eq!(result.err().map(|(n, _)| n), Some(2));
+}
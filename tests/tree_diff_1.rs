@@ -0,0 +1,75 @@
+use lemon_tree::{lem_fn, LemonTree, LemonTreeNode, tree_diff, assert_tree_eq};
+
+#[derive(LemonTreeNode, Debug, PartialEq)]
+pub enum Expr
+{	#[lem("NUM(0)")] Num(f64),
+	#[lem("Expr(0) PLUS Expr(1)")] Plus(Box<Expr>, Box<Expr>),
+	#[lem("Expr(0) TIMES Expr(1)")] Times(Box<Expr>, Box<Expr>),
+}
+#[lem_fn("PAR_OPEN Expr(0) PAR_CLOSE")] pub fn expr_from_par(a: Expr) -> Expr {a}
+
+#[derive(LemonTree, Debug, PartialEq)]
+#[lem_opt(token_type="f64", left="PLUS", left="TIMES")]
+#[lem("Expr(expr)")]
+pub struct Program
+{	expr: Expr,
+}
+
+fn parse(parser: &mut <Program as LemonTree>::Parser, mut input: &str) -> Program
+{	loop
+	{	input = input.trim_start();
+		match input.bytes().next()
+		{	Some(c) => match c
+			{	b'+' => parser.add_token(<Program as LemonTree>::Token::PLUS, 0.0).unwrap(),
+				b'*' => parser.add_token(<Program as LemonTree>::Token::TIMES, 0.0).unwrap(),
+				b'(' => parser.add_token(<Program as LemonTree>::Token::PAR_OPEN, 0.0).unwrap(),
+				b')' => parser.add_token(<Program as LemonTree>::Token::PAR_CLOSE, 0.0).unwrap(),
+				b'0' ..= b'9' | b'.' =>
+				{	let pos = input.bytes().position(|c| !c.is_ascii_digit() && c!=b'.').unwrap_or(input.len());
+					let value = input[.. pos].parse().unwrap();
+					parser.add_token(<Program as LemonTree>::Token::NUM, value).unwrap();
+					input = &input[pos-1 ..];
+				}
+				_ => panic!("Invalid token")
+			}
+			None =>
+			{	return parser.end().unwrap();
+			}
+		}
+		input = &input[1 ..];
+	}
+}
+
+#[test]
+fn tree_diff_1()
+{	use Expr::*;
+	let mut parser = Program::get_parser(());
+
+	assert_tree_eq!
+	(	parse(&mut parser, "1 + 2 * 3"),
+		Program {expr: Plus(Box::new(Num(1.0)), Box::new(Times(Box::new(Num(2.0)), Box::new(Num(3.0)))))}
+	);
+
+	let diff = tree_diff
+	(	&parse(&mut parser, "(1 + 2) * 3"),
+		&Program {expr: Plus(Box::new(Num(1.0)), Box::new(Times(Box::new(Num(2.0)), Box::new(Num(3.0)))))}
+	);
+	let diff = diff.unwrap();
+	assert!(diff.contains("-     expr: Times(\n"));
+	assert!(diff.contains("+     expr: Plus(\n"));
+	assert!(diff.lines().all(|line| line.starts_with("  ") || line.starts_with("- ") || line.starts_with("+ ")));
+}
+
+#[test]
+fn tree_diff_large()
+{	use Expr::*;
+	// 20000 nodes, about 200000 lines of Debug output, that differ in one leaf
+	let left: Vec<Expr> = (0 .. 20000).map(|i| Plus(Box::new(Num(i as f64)), Box::new(Times(Box::new(Num(2.0)), Box::new(Num(3.0)))))).collect();
+	let mut right: Vec<Expr> = (0 .. 20000).map(|i| Plus(Box::new(Num(i as f64)), Box::new(Times(Box::new(Num(2.0)), Box::new(Num(3.0)))))).collect();
+	right[12345] = Plus(Box::new(Num(12345.0)), Box::new(Times(Box::new(Num(2.0)), Box::new(Num(4.0)))));
+
+	let diff = tree_diff(&left, &right).unwrap();
+	assert_eq!(diff.lines().filter(|line| line.starts_with("- ")).map(|line| line[1 ..].trim()).collect::<Vec<_>>(), vec!["3.0,"]);
+	assert_eq!(diff.lines().filter(|line| line.starts_with("+ ")).map(|line| line[1 ..].trim()).collect::<Vec<_>>(), vec!["4.0,"]);
+	assert!(diff.lines().count() < 20);
+}